@@ -28,6 +28,41 @@ by the WhiteboxTools library:
 | -v                | Verbose mode. Without this flag, tool outputs will not be printed.                                |
 | --viewcode        | Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".                       |
 | --version         | Prints the version information.                                                                   |
+| --set             | Sets a persistent global setting; --set=max_procs=4.                                              |
+| --get             | Prints the value of a persistent global setting; --get=max_procs.                                 |
+| --pipeline        | Runs a chain of tools described in a JSON workflow file; --pipeline=workflow.json.                 |
+| --generate_wrappers | Emits a QGIS or Galaxy tool descriptor for every tool; --generate_wrappers=qgis.                 |
+| --on_error        | Sets the batch error handling mode for glob inputs, continue or stop; --on_error=continue.        |
+| --interactive     | Prompts on stdin for any required parameter missing from --run; used with --run.                  |
+
+When a tool's `-i`/`--input` argument contains a wildcard (e.g. a folder of
+tiles named `tile1.tif`, `tile2.tif`, ... matched with `-i="tiles\*.tif"`),
+WhiteboxTools expands it and runs the tool once per matching file across a
+thread pool bounded by the `max_procs` setting,
+substituting a per-file `--output` name. By default the first failing file
+aborts the batch; pass `--on_error=continue` to collect failures and keep
+processing the rest.
+
+Global settings such as the default working directory, verbosity, maximum
+number of processors, and whether newly created rasters are compressed are
+stored persistently in a `settings.json` file next to the executable. These
+are read at start-up and used as defaults for any option not explicitly
+supplied on the command line, and can be viewed or updated with `--get` and
+`--set`. `ToolManager` is constructed with the resolved `Settings`, and
+exposes `compress_rasters()`. The setting is passed on to plugins as a
+`--compress_rasters` flag; built-in tools do not receive it, since
+`WhiteboxTool::run` takes only the arguments, working directory and
+verbosity.
+
+In addition to its built-in tools, WhiteboxTools discovers third-party
+plugins by scanning a `plugins` directory next to the executable for
+standalone binaries (see `utils::plugins`). A conforming plugin reports its
+name, description, toolbox, and parameters when invoked with `--json`, and
+is then listed and dispatched alongside the compiled-in tools.
+
+`--interactive` has no `-i` short form, since that's already the
+conventional short form of a tool's own `-i`/`--input` argument (including
+the space-separated `-i input.tif` form); only the long flag is recognized.
 
 */
 
@@ -43,10 +78,17 @@ pub mod utils;
 pub mod vector;
 
 use crate::tools::ToolManager;
+use crate::utils::parameter_descriptor::{ToolParameters, UNSPECIFIED_SENTINEL};
+use crate::utils::wrappers::{self, WrapperKind};
+use crate::utils::Settings;
 use nalgebra as na;
+use std::collections::{HashMap, VecDeque};
 use std::env;
-use std::io::Error;
+use std::fs::File;
+use std::io::{self, BufReader, Error, ErrorKind, Write};
 use std::path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
 #[macro_use]
 extern crate serde_derive;
@@ -86,6 +128,8 @@ fn main() {
 // }
 
 fn run() -> Result<(), Error> {
+    let mut settings = Settings::new();
+
     let sep: &str = &path::MAIN_SEPARATOR.to_string();
     let mut working_dir = String::new();
     let mut tool_name = String::new();
@@ -99,6 +143,11 @@ fn run() -> Result<(), Error> {
     let mut tool_args_vec: Vec<String> = vec![];
     let mut verbose = false;
     let mut finding_working_dir = false;
+    let mut set_setting = String::new();
+    let mut get_setting = String::new();
+    let mut pipeline_file = String::new();
+    let mut wrapper_kind = String::new();
+    let mut on_error = String::from("stop");
 
     let _matches = App::new("WhiteboxTools")
         .version(crate_version!())
@@ -125,6 +174,17 @@ fn run() -> Result<(), Error> {
         ).arg(Arg::with_name("viewcode")
         ).arg(Arg::with_name("version")
             .short("V")
+        ).arg(Arg::with_name("set")
+            .takes_value(true)
+        ).arg(Arg::with_name("get")
+            .takes_value(true)
+        ).arg(Arg::with_name("pipeline")
+            .takes_value(true)
+        ).arg(Arg::with_name("generate_wrappers")
+            .takes_value(true)
+        ).arg(Arg::with_name("on_error")
+            .takes_value(true)
+        ).arg(Arg::with_name("interactive")
         )
         .get_matches();
 
@@ -135,11 +195,22 @@ fn run() -> Result<(), Error> {
         // print help
         help();
         // list tools
-        let tm = ToolManager::new(&working_dir, &verbose)?;
+        if working_dir.trim().is_empty() {
+            working_dir = settings.working_directory.clone();
+        }
+        let tm = ToolManager::new(&working_dir, &verbose, &settings)?;
         tm.list_tools();
 
         return Ok(());
     }
+
+    // Determined up front (rather than during the arg loop below) so that
+    // whether a sentinel-valued argument is kept for re-prompting doesn't
+    // depend on `--interactive` appearing before it on the command line.
+    let interactive = args
+        .iter()
+        .any(|a| a.trim() == "--interactive" || a.trim() == "-interactive");
+
     for arg in args {
         let flag_val = arg.to_lowercase().replace("--", "-");
         if flag_val == "-h" || flag_val == "-help" {
@@ -225,6 +296,56 @@ fn run() -> Result<(), Error> {
             }
             tool_name = v;
             view_code = true;
+        } else if arg.starts_with("-pipeline") || arg.starts_with("--pipeline") {
+            let mut v = arg
+                .replace("--pipeline", "")
+                .replace("-pipeline", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            pipeline_file = v;
+        } else if arg.starts_with("-generate_wrappers") || arg.starts_with("--generate_wrappers") {
+            let mut v = arg
+                .replace("--generate_wrappers", "")
+                .replace("-generate_wrappers", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            wrapper_kind = v;
+        } else if arg.starts_with("-set") || arg.starts_with("--set") {
+            let mut v = arg
+                .replace("--set", "")
+                .replace("-set", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            set_setting = v;
+        } else if arg.starts_with("-get") || arg.starts_with("--get") {
+            let mut v = arg
+                .replace("--get", "")
+                .replace("-get", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            get_setting = v;
+        } else if arg.starts_with("-on_error") || arg.starts_with("--on_error") {
+            let mut v = arg
+                .replace("--on_error", "")
+                .replace("-on_error", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            on_error = v;
         } else if arg.starts_with("-license")
             || arg.starts_with("-licence")
             || arg.starts_with("--license")
@@ -238,14 +359,19 @@ fn run() -> Result<(), Error> {
             return Ok(());
         } else if arg.trim() == "-v" {
             verbose = true;
+        } else if arg.trim() == "--interactive" || arg.trim() == "-interactive" {
+            // Already folded into `interactive` above; consume the flag
+            // here too so it isn't forwarded to the tool as an argument below.
         } else if arg.starts_with("-") {
             // it's an arg to be fed to the tool
-            if !arg.contains("-17976931348623157") {
-                // The QGIS plugin doesn't seem to handle numerical arguments that don't supply default values very well.
-                // When this is the case, it will use an extremely large negative value, starting with the sequence above,
-                // as the default. So if this number occurs in the argument, it means that the value was unspecified. If
-                // it's an optional parameter, the tool will be able to handle this situation. If not, an error will likely
-                // be thrown by the absence of the parameter.
+            //
+            // The QGIS plugin doesn't seem to handle numerical arguments that don't supply default values very well.
+            // When this is the case, it will use an extremely large negative value, starting with `UNSPECIFIED_SENTINEL`,
+            // as the default. So if this number occurs in the argument, it means that the value was unspecified. If
+            // it's an optional parameter, the tool will be able to handle this situation. If not, an error will likely
+            // be thrown by the absence of the parameter. In interactive mode the sentinel is kept instead of dropped,
+            // so `prompt_for_missing_params` can detect it and ask the user for a real value.
+            if should_keep_tool_arg(&arg, interactive) {
                 tool_args_vec.push(arg.trim().to_string().clone());
             }
         } else if !arg.contains("whitebox_tools") {
@@ -266,15 +392,51 @@ fn run() -> Result<(), Error> {
         }
     }
 
+    if !set_setting.is_empty() {
+        let parts: Vec<&str> = set_setting.splitn(2, '=').collect();
+        if parts.len() != 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "--set expects the form key=value, e.g. --set=max_procs=4",
+            ));
+        }
+        settings.set(parts[0], parts[1])?;
+        return Ok(());
+    } else if !get_setting.is_empty() {
+        match settings.get(&get_setting) {
+            Some(v) => println!("{}", v),
+            None => println!("Unrecognized setting '{}'", get_setting),
+        }
+        return Ok(());
+    }
+
+    if working_dir.trim().is_empty() {
+        working_dir = settings.working_directory.clone();
+    }
+    if !verbose {
+        verbose = settings.verbose;
+    }
+
     let sep = path::MAIN_SEPARATOR;
-    if !working_dir.ends_with(sep) {
+    if !working_dir.is_empty() && !working_dir.ends_with(sep) {
         working_dir.push_str(&(sep.to_string()));
     }
-    let tm = ToolManager::new(&working_dir, &verbose)?;
-    if run_tool {
+    let tm = ToolManager::new(&working_dir, &verbose, &settings)?;
+    if !pipeline_file.is_empty() {
+        return run_pipeline(&tm, &pipeline_file);
+    } else if !wrapper_kind.is_empty() {
+        return generate_wrappers(&tm, &wrapper_kind);
+    } else if run_tool {
         if tool_name.is_empty() && keywords.len() > 0 {
             tool_name = keywords[0].clone();
         }
+        if interactive {
+            prompt_for_missing_params(&tm, &tool_name, &mut tool_args_vec)?;
+        }
+        if let Some(pattern) = glob_input_pattern(&tool_args_vec) {
+            let on_error = OnError::from_str(&on_error)?;
+            return run_tool_over_glob(&tm, tool_name, tool_args_vec, &pattern, settings.max_procs, on_error);
+        }
         return tm.run_tool(tool_name, tool_args_vec);
     } else if tool_help {
         if tool_name.is_empty() && keywords.len() > 0 {
@@ -310,6 +472,314 @@ fn run() -> Result<(), Error> {
     Ok(())
 }
 
+/// A single step of a `--pipeline` workflow file, naming the tool to run
+/// and the argument vector to pass it, e.g.
+/// `{ "tool": "FillDepressions", "args": ["--input=dem.tif", "--output=filled.tif"] }`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PipelineStep {
+    tool: String,
+    args: Vec<String>,
+}
+
+/// Runs each step of a `--pipeline` workflow file in sequence, substituting
+/// `${stepN.output}` tokens with the `--output` value captured from step
+/// `N`, and stopping at the first step that returns an `Err`.
+fn run_pipeline(tm: &ToolManager, pipeline_file: &str) -> Result<(), Error> {
+    let file = File::open(pipeline_file)?;
+    let reader = BufReader::new(file);
+    let steps: Vec<PipelineStep> = serde_json::from_reader(reader)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut step_outputs: HashMap<String, String> = HashMap::new();
+    for (i, step) in steps.iter().enumerate() {
+        let args: Vec<String> = step
+            .args
+            .iter()
+            .map(|a| substitute_step_tokens(a, &step_outputs))
+            .collect();
+
+        if let Some(v) = capture_step_output(&args) {
+            step_outputs.insert(format!("step{}.output", i), v);
+        }
+
+        tm.run_tool(step.tool.clone(), args)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the value of a step's `-o`/`--output` argument, if present, so
+/// it can be recorded for substitution into later steps' `${stepN.output}`
+/// tokens. Matches both the short and long flag, as `glob_input_pattern`
+/// and `args_for_glob_file` already do for `-i`/`--input`.
+fn capture_step_output(args: &[String]) -> Option<String> {
+    let output_arg = args.iter().find(|a| {
+        let flag = a.to_lowercase();
+        flag.starts_with("-o=") || flag.starts_with("--output=")
+    })?;
+    Some(
+        output_arg
+            .replacen("--output=", "", 1)
+            .replacen("-o=", "", 1)
+            .replace("\"", ""),
+    )
+}
+
+/// Replaces every `${stepN.output}`-style token in `arg` with the
+/// corresponding value from `step_outputs` (keyed by `stepN.output`,
+/// without the surrounding `${}`). Tokens with no matching entry are left
+/// untouched.
+fn substitute_step_tokens(arg: &str, step_outputs: &HashMap<String, String>) -> String {
+    let mut arg = arg.to_string();
+    for (token, output) in step_outputs {
+        arg = arg.replace(&format!("${{{}}}", token), output);
+    }
+    arg
+}
+
+/// Emits a QGIS Processing algorithm XML or Galaxy tool XML descriptor for
+/// every tool registered with `tm`, writing one file per tool to a
+/// `wrappers` directory in the current working directory.
+fn generate_wrappers(tm: &ToolManager, kind: &str) -> Result<(), Error> {
+    let kind = WrapperKind::from_str(kind)?;
+
+    let out_dir = path::Path::new("wrappers");
+    std::fs::create_dir_all(&out_dir)?;
+
+    for tool_name in tm.get_tool_names() {
+        let params_json = tm.tool_parameters_json(&tool_name)?;
+        let wrapper = wrappers::generate_wrapper(&tool_name, &params_json, kind)?;
+        let out_file = out_dir.join(format!("{}.{}", tool_name, kind.file_extension()));
+        std::fs::write(out_file, wrapper)?;
+    }
+
+    Ok(())
+}
+
+/// How a glob-expanded batch run handles a file that fails: `Stop` aborts
+/// the whole batch on the first failure (the default), while `Continue`
+/// collects failures and keeps processing the remaining files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnError {
+    Stop,
+    Continue,
+}
+
+impl OnError {
+    fn from_str(s: &str) -> Result<OnError, Error> {
+        match s.to_lowercase().as_str() {
+            "stop" => Ok(OnError::Stop),
+            "continue" => Ok(OnError::Continue),
+            v => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized --on_error value '{}'; expected 'continue' or 'stop'", v),
+            )),
+        }
+    }
+}
+
+/// Whether a tool argument should be forwarded to `tool_args_vec`: ordinary
+/// arguments always are, but one carrying the QGIS "unspecified" sentinel is
+/// dropped unless `interactive` mode is on, in which case it's kept so
+/// `prompt_for_missing_params` can detect it and re-prompt for a real value.
+fn should_keep_tool_arg(arg: &str, interactive: bool) -> bool {
+    !arg.contains(UNSPECIFIED_SENTINEL) || interactive
+}
+
+/// Returns the value of a tool's `-i`/`--input` argument if it contains a
+/// wildcard (`*` or `?`), signalling that the tool should be run once per
+/// matching file rather than once on the literal argument.
+fn glob_input_pattern(tool_args_vec: &[String]) -> Option<String> {
+    for arg in tool_args_vec {
+        let flag = arg.to_lowercase();
+        if flag.starts_with("-i=") || flag.starts_with("--input=") {
+            let mut v = arg.replacen("--input=", "", 1).replacen("-i=", "", 1);
+            v = v.replace("\"", "").replace("\'", "");
+            if v.contains('*') || v.contains('?') {
+                return Some(v);
+            }
+        }
+    }
+    None
+}
+
+/// Derives a per-file `--output` path from a template by inserting the
+/// input file's stem, e.g. an `output.tif` template and input `tile7.tif`
+/// becomes `output_tile7.tif`.
+fn derive_output_name(template: &str, input_stem: &str) -> String {
+    let path = path::Path::new(template);
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or(template);
+    let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+    let file_name = if ext.is_empty() {
+        format!("{}_{}", stem, input_stem)
+    } else {
+        format!("{}_{}.{}", stem, input_stem, ext)
+    };
+    dir.join(file_name).to_string_lossy().to_string()
+}
+
+/// Builds the per-file argument vector for a glob batch run, substituting
+/// `file` for the template's `-i`/`--input` entry and deriving a per-file
+/// `--output` name from the template's `-o`/`--output` entry.
+fn args_for_glob_file(args_template: &[String], file: &path::Path) -> Vec<String> {
+    let input_stem = file
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("file")
+        .to_string();
+    args_template
+        .iter()
+        .map(|a| {
+            let flag = a.to_lowercase();
+            if flag.starts_with("-i=") || flag.starts_with("--input=") {
+                format!("-i={}", file.to_string_lossy())
+            } else if flag.starts_with("-o=") || flag.starts_with("--output=") {
+                let template = a.replacen("--output=", "", 1).replacen("-o=", "", 1);
+                format!("-o={}", derive_output_name(&template, &input_stem))
+            } else {
+                a.clone()
+            }
+        })
+        .collect()
+}
+
+/// Expands a wildcard `-i`/`--input` argument and runs `tool_name` once per
+/// matching file across a bounded pool of `max_procs` worker threads
+/// (values <= 0 use all available cores), substituting a per-file
+/// `--output` name. Each worker pulls the next pending file as soon as it
+/// finishes its current one, so a slow file never stalls idle workers.
+/// With `OnError::Stop`, the first failure stops workers from picking up
+/// further files, though whichever file each worker already had in flight
+/// still runs to completion.
+fn run_tool_over_glob(
+    tm: &ToolManager,
+    tool_name: String,
+    args_template: Vec<String>,
+    pattern: &str,
+    max_procs: isize,
+    on_error: OnError,
+) -> Result<(), Error> {
+    // Requires the `glob` crate as a dependency.
+    let files: VecDeque<path::PathBuf> = glob::glob(pattern)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?
+        .filter_map(|p| p.ok())
+        .collect();
+
+    if files.is_empty() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("No files matched the glob pattern '{}'", pattern),
+        ));
+    }
+
+    let pool_size = if max_procs > 0 {
+        max_procs as usize
+    } else {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    }
+    .min(files.len());
+
+    let queue = Mutex::new(files);
+    let aborted = AtomicBool::new(false);
+    let failures: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..pool_size {
+            scope.spawn(|| loop {
+                if on_error == OnError::Stop && aborted.load(Ordering::SeqCst) {
+                    break;
+                }
+                let file = match queue.lock().unwrap().pop_front() {
+                    Some(f) => f,
+                    None => break,
+                };
+
+                let args = args_for_glob_file(&args_template, &file);
+                if let Err(e) = tm.run_tool(tool_name.clone(), args) {
+                    failures
+                        .lock()
+                        .unwrap()
+                        .push(format!("{}: {}", file.to_string_lossy(), e));
+                    if on_error == OnError::Stop {
+                        aborted.store(true, Ordering::SeqCst);
+                    }
+                }
+            });
+        }
+    });
+
+    let failures = failures.into_inner().unwrap();
+    if !failures.is_empty() {
+        return Err(Error::new(ErrorKind::Other, failures.join("; ")));
+    }
+
+    Ok(())
+}
+
+/// Walks `tool_name`'s parameter metadata and, for every required parameter
+/// that is absent from `tool_args_vec` or carries the QGIS "unspecified"
+/// sentinel, prompts on stdin for a value (showing the parameter's
+/// description and default) and appends it to `tool_args_vec`.
+fn prompt_for_missing_params(
+    tm: &ToolManager,
+    tool_name: &str,
+    tool_args_vec: &mut Vec<String>,
+) -> Result<(), Error> {
+    let params_json = tm.tool_parameters_json(tool_name)?;
+    let params: ToolParameters = serde_json::from_str(&params_json)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+
+    for p in params.parameters.iter().filter(|p| !p.optional) {
+        let existing = tool_args_vec.iter().find(|a| {
+            p.flags
+                .iter()
+                .any(|f| a.to_lowercase().starts_with(&format!("{}=", f.to_lowercase())))
+        });
+        let needs_prompt = match existing {
+            None => true,
+            Some(a) => a.contains(UNSPECIFIED_SENTINEL),
+        };
+        if !needs_prompt {
+            continue;
+        }
+
+        print!("{}", p.description);
+        if let Some(d) = &p.default_value {
+            print!(" [default: {}]", d);
+        }
+        print!(": ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        let value = if input.is_empty() {
+            p.default_value.clone().unwrap_or_default()
+        } else {
+            input.to_string()
+        };
+
+        let flag = p.flags.last().cloned().unwrap_or_else(|| p.name.clone());
+        tool_args_vec.retain(|a| {
+            !p.flags
+                .iter()
+                .any(|f| a.to_lowercase().starts_with(&format!("{}=", f.to_lowercase())))
+        });
+        tool_args_vec.push(format!("{}={}", flag, value));
+    }
+
+    // Required params carrying the sentinel were just resolved above, so
+    // anything still carrying it belongs to an optional param that wasn't
+    // prompted for; drop it rather than forwarding it to `tm.run_tool`,
+    // matching non-interactive mode's handling of the same sentinel.
+    tool_args_vec.retain(|a| !a.contains(UNSPECIFIED_SENTINEL));
+
+    Ok(())
+}
+
 fn help() {
     let mut ext = "";
     if cfg!(target_os = "windows") {
@@ -332,6 +802,12 @@ The following commands are recognized:
 -v               Verbose mode. Without this flag, tool outputs will not be printed.
 --viewcode       Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".
 --version        Prints the version information.
+--set            Sets a persistent global setting; --set=max_procs=4.
+--get            Prints the value of a persistent global setting; --get=max_procs.
+--pipeline       Runs a chain of tools described in a JSON workflow file; --pipeline=workflow.json.
+--generate_wrappers Emits a QGIS or Galaxy tool descriptor for every tool; --generate_wrappers=qgis.
+--on_error       Sets the batch error handling mode for glob inputs, continue or stop; --on_error=continue.
+--interactive    Prompts on stdin for any required parameter missing from --run; used with --run.
 
 Example Usage:
 >> .*EXE_NAME -r=lidar_info --cd=\"*path*to*data*\" -i=input.las --vlr --geokeys
@@ -374,3 +850,156 @@ for more details.",
         VERSION.unwrap_or("unknown")
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_step_output_matches_long_flag() {
+        let args = vec!["--run=Slope".to_string(), "--output=dem_slope.tif".to_string()];
+        assert_eq!(capture_step_output(&args), Some("dem_slope.tif".to_string()));
+    }
+
+    #[test]
+    fn capture_step_output_matches_short_flag() {
+        let args = vec!["--run=Slope".to_string(), "-o=dem_slope.tif".to_string()];
+        assert_eq!(capture_step_output(&args), Some("dem_slope.tif".to_string()));
+    }
+
+    #[test]
+    fn capture_step_output_strips_quotes() {
+        let args = vec!["--output=\"dem slope.tif\"".to_string()];
+        assert_eq!(capture_step_output(&args), Some("dem slope.tif".to_string()));
+    }
+
+    #[test]
+    fn capture_step_output_is_none_when_absent() {
+        let args = vec!["--run=Slope".to_string(), "--input=dem.tif".to_string()];
+        assert_eq!(capture_step_output(&args), None);
+    }
+
+    #[test]
+    fn substitute_step_tokens_replaces_known_token() {
+        let mut outputs = HashMap::new();
+        outputs.insert("step0.output".to_string(), "dem_filled.tif".to_string());
+        assert_eq!(
+            substitute_step_tokens("--input=${step0.output}", &outputs),
+            "--input=dem_filled.tif"
+        );
+    }
+
+    #[test]
+    fn substitute_step_tokens_leaves_unknown_token_untouched() {
+        let outputs = HashMap::new();
+        assert_eq!(
+            substitute_step_tokens("--input=${step0.output}", &outputs),
+            "--input=${step0.output}"
+        );
+    }
+
+    #[test]
+    fn substitute_step_tokens_replaces_multiple_tokens() {
+        let mut outputs = HashMap::new();
+        outputs.insert("step0.output".to_string(), "a.tif".to_string());
+        outputs.insert("step1.output".to_string(), "b.tif".to_string());
+        assert_eq!(
+            substitute_step_tokens("${step0.output},${step1.output}", &outputs),
+            "a.tif,b.tif"
+        );
+    }
+
+    #[test]
+    fn substitute_step_tokens_with_no_tokens_is_unchanged() {
+        let outputs = HashMap::new();
+        assert_eq!(substitute_step_tokens("--verbose", &outputs), "--verbose");
+    }
+
+    #[test]
+    fn should_keep_tool_arg_keeps_ordinary_args_regardless_of_mode() {
+        assert!(should_keep_tool_arg("-i=dem.tif", false));
+        assert!(should_keep_tool_arg("-i=dem.tif", true));
+    }
+
+    #[test]
+    fn should_keep_tool_arg_drops_sentinel_outside_interactive_mode() {
+        let arg = format!("--threshold={}", UNSPECIFIED_SENTINEL);
+        assert!(!should_keep_tool_arg(&arg, false));
+    }
+
+    #[test]
+    fn should_keep_tool_arg_keeps_sentinel_in_interactive_mode() {
+        let arg = format!("--threshold={}", UNSPECIFIED_SENTINEL);
+        assert!(should_keep_tool_arg(&arg, true));
+    }
+
+    #[test]
+    fn on_error_from_str_accepts_known_values_case_insensitively() {
+        assert_eq!(OnError::from_str("stop").unwrap(), OnError::Stop);
+        assert_eq!(OnError::from_str("STOP").unwrap(), OnError::Stop);
+        assert_eq!(OnError::from_str("continue").unwrap(), OnError::Continue);
+        assert_eq!(OnError::from_str("Continue").unwrap(), OnError::Continue);
+    }
+
+    #[test]
+    fn on_error_from_str_rejects_unknown_value() {
+        assert!(OnError::from_str("ignore").is_err());
+    }
+
+    #[test]
+    fn derive_output_name_inserts_stem_before_extension() {
+        assert_eq!(
+            derive_output_name("output.tif", "tile7"),
+            "output_tile7.tif"
+        );
+    }
+
+    #[test]
+    fn derive_output_name_preserves_directory() {
+        assert_eq!(
+            derive_output_name("/data/out/output.tif", "tile7"),
+            "/data/out/output_tile7.tif"
+        );
+    }
+
+    #[test]
+    fn derive_output_name_handles_no_extension() {
+        assert_eq!(derive_output_name("output", "tile7"), "output_tile7");
+    }
+
+    #[test]
+    fn args_for_glob_file_substitutes_input_and_derives_output() {
+        let template = vec![
+            "-i=tiles/*.tif".to_string(),
+            "-o=output.tif".to_string(),
+            "--verbose".to_string(),
+        ];
+        let file = path::Path::new("tiles/tile7.tif");
+        let args = args_for_glob_file(&template, file);
+        assert_eq!(
+            args,
+            vec![
+                "-i=tiles/tile7.tif".to_string(),
+                "-o=output_tile7.tif".to_string(),
+                "--verbose".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn args_for_glob_file_handles_long_flag_names() {
+        let template = vec![
+            "--input=tiles/*.tif".to_string(),
+            "--output=out.tif".to_string(),
+        ];
+        let file = path::Path::new("tiles/tile3.tif");
+        let args = args_for_glob_file(&template, file);
+        assert_eq!(
+            args,
+            vec![
+                "-i=tiles/tile3.tif".to_string(),
+                "-o=out_tile3.tif".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: June 21, 2017
+Last Modified: 21/03/2019
+License: MIT
+*/
+
+use crate::utils::plugins::{self, PluginDescriptor};
+use crate::utils::Settings;
+use std::env;
+use std::io::{Error, ErrorKind};
+
+/// The interface implemented by every compiled-in tool so that
+/// `ToolManager` can list, describe, and dispatch to it uniformly.
+pub trait WhiteboxTool: Send + Sync {
+    fn name(&self) -> String;
+    fn description(&self) -> String;
+    fn toolbox(&self) -> String;
+    fn help(&self) -> String;
+    fn source_file(&self) -> String;
+    fn parameters(&self) -> String;
+    fn run(&self, args: Vec<String>, working_directory: &str, verbose: bool) -> Result<(), Error>;
+}
+
+/// Dispatches CLI requests (`--run`, `--toolhelp`, `--toolparameters`, ...)
+/// to the compiled-in tool registry, threading through the resolved
+/// working directory, verbosity, and persistent settings.
+pub struct ToolManager {
+    working_directory: String,
+    verbose: bool,
+    compress_rasters: bool,
+    tools: Vec<Box<dyn WhiteboxTool>>,
+    plugins: Vec<PluginDescriptor>,
+}
+
+impl ToolManager {
+    pub fn new(
+        working_directory: &str,
+        verbose: &bool,
+        settings: &Settings,
+    ) -> Result<ToolManager, Error> {
+        let plugins = match env::current_exe() {
+            Ok(mut exe_dir) => {
+                exe_dir.pop();
+                exe_dir.push("plugins");
+                plugins::discover_plugins(&exe_dir)
+            }
+            Err(_) => vec![],
+        };
+
+        Ok(ToolManager {
+            working_directory: working_directory.to_string(),
+            verbose: *verbose,
+            compress_rasters: settings.compress_rasters,
+            tools: vec![],
+            plugins,
+        })
+    }
+
+    fn find_plugin(&self, tool_name: &str) -> Option<&PluginDescriptor> {
+        self.plugins
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(tool_name))
+    }
+
+    /// Whether newly created rasters should be compressed, as configured
+    /// by the `compress_rasters` persistent setting. It is forwarded to
+    /// plugins as a `--compress_rasters` flag; built-in tools do not see
+    /// it, since `WhiteboxTool::run` is not passed this value.
+    pub fn compress_rasters(&self) -> bool {
+        self.compress_rasters
+    }
+
+    fn find_tool(&self, tool_name: &str) -> Option<&dyn WhiteboxTool> {
+        self.tools
+            .iter()
+            .find(|t| t.name().eq_ignore_ascii_case(tool_name))
+            .map(|t| t.as_ref())
+    }
+
+    pub fn run_tool(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        if let Some(t) = self.find_tool(&tool_name) {
+            return t.run(args, &self.working_directory, self.verbose);
+        }
+        if let Some(p) = self.find_plugin(&tool_name) {
+            return plugins::run_plugin(
+                p,
+                &self.working_directory,
+                self.verbose,
+                self.compress_rasters,
+                args,
+            );
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Unrecognized tool name '{}'", tool_name),
+        ))
+    }
+
+    pub fn tool_help(&self, tool_name: String) -> Result<(), Error> {
+        if let Some(t) = self.find_tool(&tool_name) {
+            println!("{}", t.help());
+            return Ok(());
+        }
+        if let Some(p) = self.find_plugin(&tool_name) {
+            return plugins::run_plugin(
+                p,
+                &self.working_directory,
+                self.verbose,
+                self.compress_rasters,
+                vec!["--toolhelp".to_string()],
+            );
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Unrecognized tool name '{}'", tool_name),
+        ))
+    }
+
+    pub fn tool_parameters(&self, tool_name: String) -> Result<(), Error> {
+        println!("{}", self.tool_parameters_json(&tool_name)?);
+        Ok(())
+    }
+
+    /// Returns a tool's parameters as the JSON string also printed by
+    /// `--toolparameters`, for callers (such as `--generate_wrappers`)
+    /// that need the data rather than stdout output. Plugin parameters are
+    /// rendered from the descriptor captured at discovery time.
+    pub fn tool_parameters_json(&self, tool_name: &str) -> Result<String, Error> {
+        if let Some(t) = self.find_tool(tool_name) {
+            return Ok(t.parameters());
+        }
+        if let Some(p) = self.find_plugin(tool_name) {
+            return Ok(p.parameters.to_string());
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Unrecognized tool name '{}'", tool_name),
+        ))
+    }
+
+    /// Returns the names of every tool registered with this manager,
+    /// built-in and plugin alike, used by `--generate_wrappers` to iterate
+    /// the full tool catalog.
+    pub fn get_tool_names(&self) -> Vec<String> {
+        self.tools
+            .iter()
+            .map(|t| t.name())
+            .chain(self.plugins.iter().map(|p| p.name.clone()))
+            .collect()
+    }
+
+    pub fn toolbox(&self, tool_name: String) -> Result<(), Error> {
+        if tool_name.is_empty() {
+            for t in &self.tools {
+                println!("{}: {}", t.name(), t.toolbox());
+            }
+            for p in &self.plugins {
+                println!("{}: {}", p.name, p.toolbox);
+            }
+            return Ok(());
+        }
+        if let Some(t) = self.find_tool(&tool_name) {
+            println!("{}", t.toolbox());
+            return Ok(());
+        }
+        if let Some(p) = self.find_plugin(&tool_name) {
+            println!("{}", p.toolbox);
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Unrecognized tool name '{}'", tool_name),
+        ))
+    }
+
+    pub fn list_tools(&self) {
+        for t in &self.tools {
+            println!("{}: {}", t.name(), t.description());
+        }
+        for p in &self.plugins {
+            println!("{}: {}", p.name, p.description);
+        }
+    }
+
+    pub fn list_tools_with_keywords(&self, keywords: Vec<String>) {
+        let keywords: Vec<String> = keywords.iter().map(|k| k.to_lowercase()).collect();
+        let matches = |name: &str, description: &str| {
+            let name = name.to_lowercase();
+            let description = description.to_lowercase();
+            keywords
+                .iter()
+                .any(|k| name.contains(k) || description.contains(k))
+        };
+        for t in &self.tools {
+            if matches(&t.name(), &t.description()) {
+                println!("{}: {}", t.name(), t.description());
+            }
+        }
+        for p in &self.plugins {
+            if matches(&p.name, &p.description) {
+                println!("{}: {}", p.name, p.description);
+            }
+        }
+    }
+
+    pub fn get_tool_source_code(&self, tool_name: String) -> Result<(), Error> {
+        if let Some(t) = self.find_tool(&tool_name) {
+            println!("{}", t.source_file());
+            return Ok(());
+        }
+        if self.find_plugin(&tool_name).is_some() {
+            println!(
+                "'{}' is a third-party plugin; no bundled source code to view.",
+                tool_name
+            );
+            return Ok(());
+        }
+        Err(Error::new(
+            ErrorKind::NotFound,
+            format!("Unrecognized tool name '{}'", tool_name),
+        ))
+    }
+}
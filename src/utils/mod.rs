@@ -0,0 +1,14 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 28/01/2019
+Last Modified: 28/01/2019
+License: MIT
+*/
+
+pub mod parameter_descriptor;
+pub mod plugins;
+pub mod settings;
+pub mod wrappers;
+
+pub use self::settings::Settings;
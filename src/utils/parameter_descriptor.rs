@@ -0,0 +1,49 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 14/03/2019
+Last Modified: 14/03/2019
+License: MIT
+*/
+
+/// A minimal mirror of the parameter descriptor emitted by
+/// `tm.tool_parameters`, shared by anything that needs to reason about a
+/// tool's parameters without depending on a particular GUI toolkit or
+/// front-end's data model (wrapper generation, interactive prompting, ...).
+///
+/// `parameter_type` is kept as a raw `serde_json::Value` rather than a flat
+/// `String` because WhiteboxTools' real `ParameterType` doesn't always
+/// serialize as a bare string: file parameters carry a subtype, e.g.
+/// `{"ExistingFile":"Raster"}`. Use [`ToolParameterDescriptor::type_name`]
+/// to get at the variant name regardless of which shape it came in as.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolParameterDescriptor {
+    pub name: String,
+    pub flags: Vec<String>,
+    pub description: String,
+    pub parameter_type: serde_json::Value,
+    pub default_value: Option<String>,
+    pub optional: bool,
+}
+
+impl ToolParameterDescriptor {
+    /// The parameter type's variant name, whether the JSON encodes it as a
+    /// bare string (`"Boolean"`) or as a single-key object carrying a
+    /// subtype (`{"ExistingFile":"Raster"}`).
+    pub fn type_name(&self) -> String {
+        match &self.parameter_type {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolParameters {
+    pub parameters: Vec<ToolParameterDescriptor>,
+}
+
+/// The sentinel value the QGIS plugin sends for a numerical argument it
+/// has no default for; its presence in an argument means "unspecified".
+pub const UNSPECIFIED_SENTINEL: &str = "-17976931348623157";
@@ -0,0 +1,109 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 23/02/2019
+Last Modified: 23/02/2019
+License: MIT
+*/
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Metadata reported by an external plugin executable when invoked with
+/// `--json`, mirroring enough of a built-in tool's shape (name, toolbox,
+/// description, parameters) for it to be registered and dispatched by
+/// `ToolManager` alongside the compiled-in tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginDescriptor {
+    pub name: String,
+    pub description: String,
+    pub toolbox: String,
+    pub parameters: serde_json::Value,
+    #[serde(skip)]
+    pub executable_path: PathBuf,
+}
+
+/// Scans `plugins_dir` for standalone executables and queries each with
+/// `--json` to retrieve its name, description, toolbox, and parameter
+/// metadata. Files that aren't executable, or that don't respond with
+/// valid JSON, are silently skipped rather than aborting discovery.
+pub fn discover_plugins(plugins_dir: &Path) -> Vec<PluginDescriptor> {
+    let mut plugins = vec![];
+    let entries = match fs::read_dir(plugins_dir) {
+        Ok(e) => e,
+        Err(_) => return plugins,
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !is_executable(&path) {
+            continue;
+        }
+        let output = match Command::new(&path).arg("--json").output() {
+            Ok(o) => o,
+            Err(_) => continue,
+        };
+        if !output.status.success() {
+            continue;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Ok(mut descriptor) = serde_json::from_str::<PluginDescriptor>(&stdout) {
+            descriptor.executable_path = path;
+            plugins.push(descriptor);
+        }
+    }
+
+    plugins
+}
+
+/// Runs a previously discovered plugin, translating the tool's argument
+/// vector into a call to its executable and threading through the active
+/// working directory, verbosity, and raster-compression context so it
+/// behaves consistently with a built-in tool.
+pub fn run_plugin(
+    plugin: &PluginDescriptor,
+    working_dir: &str,
+    verbose: bool,
+    compress_rasters: bool,
+    args: Vec<String>,
+) -> Result<(), Error> {
+    let mut cmd = Command::new(&plugin.executable_path);
+    cmd.arg(format!("--wd={}", working_dir));
+    if verbose {
+        cmd.arg("-v");
+    }
+    if compress_rasters {
+        cmd.arg("--compress_rasters");
+    }
+    cmd.args(&args);
+
+    let status = cmd.status()?;
+    if !status.success() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("Plugin '{}' exited with a non-zero status", plugin.name),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.is_file()
+        && fs::metadata(path)
+            .map(|m| m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .map(|e| e == "exe")
+            .unwrap_or(false)
+}
@@ -0,0 +1,195 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 28/01/2019
+Last Modified: 28/01/2019
+License: MIT
+*/
+
+use serde_json;
+use std::env;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind};
+use std::path::PathBuf;
+
+/// Persistent, global WhiteboxTools settings, loaded from (and saved to) a
+/// `settings.json` file stored next to the executable. These act as the
+/// defaults for options that would otherwise need to be repeated on every
+/// `--run` invocation, such as the working directory or verbosity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub working_directory: String,
+    pub verbose: bool,
+    pub max_procs: isize,
+    pub compress_rasters: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            working_directory: String::new(),
+            verbose: false,
+            max_procs: -1,
+            compress_rasters: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the settings from the `settings.json` file found next to the
+    /// executable. If the file doesn't exist, the default settings are
+    /// returned and no error is raised.
+    pub fn new() -> Settings {
+        match Settings::settings_file() {
+            Ok(f) => match File::open(&f) {
+                Ok(file) => {
+                    let reader = BufReader::new(file);
+                    serde_json::from_reader(reader).unwrap_or_else(|_| Settings::default())
+                }
+                Err(_) => Settings::default(),
+            },
+            Err(_) => Settings::default(),
+        }
+    }
+
+    /// Writes the current settings to the `settings.json` file found next
+    /// to the executable, creating it if it doesn't already exist.
+    pub fn save(&self) -> Result<(), Error> {
+        let f = Settings::settings_file()?;
+        let file = File::create(&f)?;
+        let writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+    }
+
+    /// Returns the value of a named setting as a string, or `None` if the
+    /// key is not recognized.
+    pub fn get(&self, key: &str) -> Option<String> {
+        match key.to_lowercase().as_str() {
+            "working_directory" => Some(self.working_directory.clone()),
+            "verbose" => Some(self.verbose.to_string()),
+            "max_procs" => Some(self.max_procs.to_string()),
+            "compress_rasters" => Some(self.compress_rasters.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Updates a named setting from a string value and persists the change
+    /// to `settings.json`. Returns an error if the key is unrecognized or
+    /// the value can't be parsed into the setting's type.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        self.set_value(key, value)?;
+        self.save()
+    }
+
+    /// The parsing/validation half of [`Settings::set`], split out so it can
+    /// be exercised without touching `settings.json` on disk.
+    fn set_value(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        match key.to_lowercase().as_str() {
+            "working_directory" => self.working_directory = value.to_string(),
+            "verbose" => {
+                self.verbose = value
+                    .parse::<bool>()
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?
+            }
+            "max_procs" => {
+                self.max_procs = value
+                    .parse::<isize>()
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?
+            }
+            "compress_rasters" => {
+                self.compress_rasters = value
+                    .parse::<bool>()
+                    .map_err(|e| Error::new(ErrorKind::InvalidInput, e.to_string()))?
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unrecognized setting '{}'", key),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// The path of the `settings.json` file, located in the same directory
+    /// as the running executable.
+    fn settings_file() -> Result<PathBuf, Error> {
+        let mut p = env::current_exe()?;
+        p.pop();
+        p.push("settings.json");
+        Ok(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_returns_none_for_unrecognized_key() {
+        let settings = Settings::default();
+        assert_eq!(settings.get("not_a_real_setting"), None);
+    }
+
+    #[test]
+    fn get_set_round_trip_for_each_setting() {
+        let mut settings = Settings::default();
+
+        settings
+            .set_value("working_directory", "/data/tiles")
+            .unwrap();
+        assert_eq!(
+            settings.get("working_directory"),
+            Some("/data/tiles".to_string())
+        );
+
+        settings.set_value("verbose", "true").unwrap();
+        assert_eq!(settings.get("verbose"), Some("true".to_string()));
+
+        settings.set_value("max_procs", "4").unwrap();
+        assert_eq!(settings.get("max_procs"), Some("4".to_string()));
+
+        settings.set_value("compress_rasters", "true").unwrap();
+        assert_eq!(settings.get("compress_rasters"), Some("true".to_string()));
+    }
+
+    #[test]
+    fn get_set_round_trip_is_case_insensitive() {
+        let mut settings = Settings::default();
+        settings.set_value("Max_Procs", "8").unwrap();
+        assert_eq!(settings.get("MAX_PROCS"), Some("8".to_string()));
+    }
+
+    #[test]
+    fn set_value_rejects_unrecognized_key() {
+        let mut settings = Settings::default();
+        assert!(settings.set_value("not_a_real_setting", "x").is_err());
+    }
+
+    #[test]
+    fn set_value_rejects_unparsable_value() {
+        let mut settings = Settings::default();
+        assert!(settings.set_value("max_procs", "not_a_number").is_err());
+        assert!(settings.set_value("verbose", "not_a_bool").is_err());
+    }
+
+    #[test]
+    fn serde_round_trip_preserves_all_fields() {
+        let mut settings = Settings::default();
+        settings
+            .set_value("working_directory", "/data/tiles")
+            .unwrap();
+        settings.set_value("verbose", "true").unwrap();
+        settings.set_value("max_procs", "2").unwrap();
+        settings.set_value("compress_rasters", "true").unwrap();
+
+        let json = serde_json::to_string(&settings).unwrap();
+        let restored: Settings = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.working_directory, settings.working_directory);
+        assert_eq!(restored.verbose, settings.verbose);
+        assert_eq!(restored.max_procs, settings.max_procs);
+        assert_eq!(restored.compress_rasters, settings.compress_rasters);
+    }
+}
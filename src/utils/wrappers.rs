@@ -0,0 +1,258 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 09/02/2019
+Last Modified: 09/02/2019
+License: MIT
+*/
+
+use crate::utils::parameter_descriptor::ToolParameters;
+use std::io::{Error, ErrorKind};
+
+/// Supported wrapper descriptor formats for `--generate_wrappers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapperKind {
+    Qgis,
+    Galaxy,
+}
+
+impl WrapperKind {
+    pub fn from_str(s: &str) -> Result<WrapperKind, Error> {
+        match s.to_lowercase().as_str() {
+            "qgis" => Ok(WrapperKind::Qgis),
+            "galaxy" => Ok(WrapperKind::Galaxy),
+            v => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized wrapper kind '{}'; expected 'qgis' or 'galaxy'", v),
+            )),
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        "xml"
+    }
+}
+
+/// Builds a wrapper descriptor for `tool_name`, given its parameters as
+/// the JSON produced by `tm.tool_parameters`, in the format requested by
+/// `kind`.
+pub fn generate_wrapper(tool_name: &str, parameters_json: &str, kind: WrapperKind) -> Result<String, Error> {
+    let params: ToolParameters = serde_json::from_str(parameters_json)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+
+    Ok(match kind {
+        WrapperKind::Qgis => generate_qgis_wrapper(tool_name, &params),
+        WrapperKind::Galaxy => generate_galaxy_wrapper(tool_name, &params),
+    })
+}
+
+/// Escapes the characters that are significant inside an XML attribute
+/// value (`&`, `<`, `>`, `"`) so that tool descriptions/defaults containing
+/// them don't produce malformed wrapper XML.
+fn escape_xml_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn qgis_widget_for(parameter_type: &str) -> &'static str {
+    match parameter_type {
+        "Boolean" => "ParameterBoolean",
+        "Integer" => "ParameterNumber",
+        "Float" => "ParameterNumber",
+        "ExistingFile" => "ParameterFile",
+        "NewFile" => "ParameterFileDestination",
+        _ => "ParameterString",
+    }
+}
+
+fn generate_qgis_wrapper(tool_name: &str, params: &ToolParameters) -> String {
+    let mut s = String::new();
+    s.push_str(&format!("<tool name=\"{}\">\n", escape_xml_attr(tool_name)));
+    for p in &params.parameters {
+        let flag = p.flags.last().map(|f| f.as_str()).unwrap_or("");
+        s.push_str(&format!(
+            "  <{widget} name=\"{flag}\" description=\"{desc}\" optional=\"{opt}\" default=\"{def}\" />\n",
+            widget = qgis_widget_for(&p.type_name()),
+            flag = escape_xml_attr(flag.trim_start_matches('-')),
+            desc = escape_xml_attr(&p.description),
+            opt = p.optional,
+            def = escape_xml_attr(&p.default_value.clone().unwrap_or_default()),
+        ));
+    }
+    s.push_str("</tool>\n");
+    s
+}
+
+fn galaxy_param_for(parameter_type: &str) -> &'static str {
+    match parameter_type {
+        "Boolean" => "boolean",
+        "Integer" => "integer",
+        "Float" => "float",
+        "ExistingFile" => "data",
+        "NewFile" => "data",
+        _ => "text",
+    }
+}
+
+fn generate_galaxy_wrapper(tool_name: &str, params: &ToolParameters) -> String {
+    let mut s = String::new();
+    s.push_str(&format!(
+        "<tool id=\"{0}\" name=\"{0}\">\n  <command>whitebox_tools --run={0}",
+        escape_xml_attr(tool_name)
+    ));
+    for p in &params.parameters {
+        let flag = p.flags.last().map(|f| f.as_str()).unwrap_or("");
+        s.push_str(&format!(" {}=\"${}\"", flag, p.name));
+    }
+    s.push_str("</command>\n  <inputs>\n");
+    for p in &params.parameters {
+        s.push_str(&format!(
+            "    <param name=\"{name}\" type=\"{ptype}\" optional=\"{opt}\" label=\"{desc}\" />\n",
+            name = escape_xml_attr(&p.name),
+            ptype = galaxy_param_for(&p.type_name()),
+            opt = p.optional,
+            desc = escape_xml_attr(&p.description),
+        ));
+    }
+    s.push_str("  </inputs>\n</tool>\n");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parameter_descriptor::ToolParameterDescriptor;
+
+    fn sample_params() -> ToolParameters {
+        ToolParameters {
+            parameters: vec![
+                ToolParameterDescriptor {
+                    name: "input".to_string(),
+                    flags: vec!["-i".to_string(), "--input".to_string()],
+                    description: "Input raster file".to_string(),
+                    parameter_type: serde_json::json!({"ExistingFile": "Raster"}),
+                    default_value: None,
+                    optional: false,
+                },
+                ToolParameterDescriptor {
+                    name: "threshold".to_string(),
+                    flags: vec!["--threshold".to_string()],
+                    description: "Threshold value".to_string(),
+                    parameter_type: serde_json::Value::String("Float".to_string()),
+                    default_value: Some("1.5".to_string()),
+                    optional: true,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn from_str_accepts_known_kinds_case_insensitively() {
+        assert_eq!(WrapperKind::from_str("qgis").unwrap(), WrapperKind::Qgis);
+        assert_eq!(WrapperKind::from_str("QGIS").unwrap(), WrapperKind::Qgis);
+        assert_eq!(
+            WrapperKind::from_str("Galaxy").unwrap(),
+            WrapperKind::Galaxy
+        );
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_kind() {
+        assert!(WrapperKind::from_str("arcgis").is_err());
+    }
+
+    #[test]
+    fn file_extension_is_xml_for_all_kinds() {
+        assert_eq!(WrapperKind::Qgis.file_extension(), "xml");
+        assert_eq!(WrapperKind::Galaxy.file_extension(), "xml");
+    }
+
+    #[test]
+    fn type_name_handles_bare_string_and_nested_subtype() {
+        let file_param = &sample_params().parameters[0];
+        assert_eq!(file_param.type_name(), "ExistingFile");
+
+        let float_param = &sample_params().parameters[1];
+        assert_eq!(float_param.type_name(), "Float");
+    }
+
+    #[test]
+    fn qgis_wrapper_contains_tool_name_and_parameters() {
+        let params = sample_params();
+        let wrapper = generate_qgis_wrapper("slope", &params);
+        assert!(wrapper.starts_with("<tool name=\"slope\">\n"));
+        assert!(wrapper.contains("ParameterFile"));
+        assert!(wrapper.contains("name=\"input\""));
+        assert!(wrapper.contains("ParameterNumber"));
+        assert!(wrapper.contains("default=\"1.5\""));
+        assert!(wrapper.ends_with("</tool>\n"));
+    }
+
+    #[test]
+    fn qgis_wrapper_escapes_special_characters_in_attributes() {
+        let params = ToolParameters {
+            parameters: vec![ToolParameterDescriptor {
+                name: "input".to_string(),
+                flags: vec!["-i".to_string()],
+                description: "Uses \"quotes\", <tags> & ampersands".to_string(),
+                parameter_type: serde_json::Value::String("Boolean".to_string()),
+                default_value: Some("A & B".to_string()),
+                optional: false,
+            }],
+        };
+        let wrapper = generate_qgis_wrapper("slope", &params);
+        assert!(wrapper
+            .contains("description=\"Uses &quot;quotes&quot;, &lt;tags&gt; &amp; ampersands\""));
+        assert!(wrapper.contains("default=\"A &amp; B\""));
+        assert!(!wrapper.contains("<tags>"));
+    }
+
+    #[test]
+    fn galaxy_wrapper_escapes_special_characters_in_attributes() {
+        let params = ToolParameters {
+            parameters: vec![ToolParameterDescriptor {
+                name: "input".to_string(),
+                flags: vec!["-i".to_string()],
+                description: "Uses \"quotes\" & <tags>".to_string(),
+                parameter_type: serde_json::Value::String("Boolean".to_string()),
+                default_value: None,
+                optional: false,
+            }],
+        };
+        let wrapper = generate_galaxy_wrapper("slope", &params);
+        assert!(wrapper.contains("label=\"Uses &quot;quotes&quot; &amp; &lt;tags&gt;\""));
+        assert!(!wrapper.contains("<tags>"));
+    }
+
+    #[test]
+    fn galaxy_wrapper_contains_tool_id_command_and_inputs() {
+        let params = sample_params();
+        let wrapper = generate_galaxy_wrapper("slope", &params);
+        assert!(wrapper.contains("<tool id=\"slope\" name=\"slope\">"));
+        assert!(wrapper.contains("whitebox_tools --run=slope"));
+        assert!(wrapper.contains("--input=\"$input\""));
+        assert!(wrapper.contains("type=\"data\""));
+        assert!(wrapper.contains("type=\"float\""));
+        assert!(wrapper.ends_with("</tool>\n"));
+    }
+
+    #[test]
+    fn generate_wrapper_dispatches_on_kind() {
+        let params = sample_params();
+        let json = serde_json::to_string(&params).unwrap();
+
+        let qgis = generate_wrapper("slope", &json, WrapperKind::Qgis).unwrap();
+        assert!(qgis.contains("ParameterFile"));
+
+        let galaxy = generate_wrapper("slope", &json, WrapperKind::Galaxy).unwrap();
+        assert!(galaxy.contains("<inputs>"));
+    }
+
+    #[test]
+    fn generate_wrapper_rejects_invalid_parameters_json() {
+        assert!(generate_wrapper("slope", "not json", WrapperKind::Qgis).is_err());
+    }
+}